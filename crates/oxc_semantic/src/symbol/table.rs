@@ -1,4 +1,7 @@
+use std::marker::PhantomData;
 use std::ops::{Deref, Index, IndexMut};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
 
 use oxc_ast::{Atom, Span};
 
@@ -7,6 +10,111 @@ use super::{Symbol, SymbolFlags, SymbolId};
 use crate::node::AstNodeId;
 use crate::{Reference, ResolvedReference};
 
+/// A type that indexes an [`IndexVec`].
+///
+/// Both `SymbolId` and `ResolvedReferenceId` are one-based (`0` is reserved), so
+/// the conversion folds the `+ 1` / `- 1` offset that used to live at every call
+/// site into a single place.
+pub trait Idx: Copy {
+    fn from_usize(index: usize) -> Self;
+    fn index(self) -> usize;
+}
+
+impl Idx for SymbolId {
+    fn from_usize(index: usize) -> Self {
+        Self::new(index + 1)
+    }
+
+    fn index(self) -> usize {
+        self.index0()
+    }
+}
+
+impl Idx for ResolvedReferenceId {
+    fn from_usize(index: usize) -> Self {
+        Self::new(index + 1)
+    }
+
+    fn index(self) -> usize {
+        self.index0()
+    }
+}
+
+/// A `Vec` indexed by a typed `Idx` newtype rather than a bare `usize`.
+///
+/// Modelled on `rustc_data_structures::IndexVec`, this makes it impossible to
+/// index the reference store with a `SymbolId` (or vice versa) and keeps the
+/// one-based offset arithmetic in [`push`](IndexVec::push) and the `Index`
+/// implementations instead of scattered across callers.
+#[derive(Debug, Clone)]
+pub struct IndexVec<I, T> {
+    raw: Vec<T>,
+    _marker: PhantomData<fn(I) -> I>,
+}
+
+impl<I, T> Default for IndexVec<I, T> {
+    fn default() -> Self {
+        Self { raw: Vec::new(), _marker: PhantomData }
+    }
+}
+
+impl<I: Idx, T> IndexVec<I, T> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self { raw: Vec::with_capacity(capacity), _marker: PhantomData }
+    }
+
+    /// The index the next [`push`](Self::push) will return.
+    #[must_use]
+    pub fn next_index(&self) -> I {
+        I::from_usize(self.raw.len())
+    }
+
+    /// Append `value`, returning its freshly allocated index.
+    pub fn push(&mut self, value: T) -> I {
+        let index = self.next_index();
+        self.raw.push(value);
+        index
+    }
+
+    /// Reserve capacity for at least `additional` more elements.
+    pub fn reserve(&mut self, additional: usize) {
+        self.raw.reserve(additional);
+    }
+
+    #[must_use]
+    pub fn get(&self, index: I) -> Option<&T> {
+        self.raw.get(index.index())
+    }
+}
+
+impl<I, T> Deref for IndexVec<I, T> {
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        &self.raw
+    }
+}
+
+impl<I: Idx, T> Index<I> for IndexVec<I, T> {
+    type Output = T;
+
+    fn index(&self, index: I) -> &Self::Output {
+        &self.raw[index.index()]
+    }
+}
+
+impl<I: Idx, T> IndexMut<I> for IndexVec<I, T> {
+    fn index_mut(&mut self, index: I) -> &mut Self::Output {
+        &mut self.raw[index.index()]
+    }
+}
+
 /// `SymbolTable` is a storage of all the symbols (related to `BindingIdentifiers`)
 /// and references (related to `IdentifierReferences`) of the program. It supports two
 /// kinds of queries: indexing by `SymbolId` retrieves the corresponding `Symbol` and
@@ -15,22 +123,97 @@ use crate::{Reference, ResolvedReference};
 #[derive(Debug, Default)]
 pub struct SymbolTable {
     /// Stores all the `Symbols` indexed by `SymbolId`
-    symbols: Vec<Symbol>,
+    symbols: IndexVec<SymbolId, Symbol>,
     /// Stores all the resolved references indexed by `ResolvedReferenceId`
-    resolved_references: Vec<ResolvedReference>,
+    resolved_references: IndexVec<ResolvedReferenceId, ResolvedReference>,
+    /// Auxiliary name index used to answer \"find every declaration named X\"
+    /// without a linear scan of `symbols`. Entries are pushed in binding order
+    /// (an O(1) append) and sorted by `lower` lazily, the first time a query
+    /// needs the sorted view after a symbol was bound.
+    name_entries: Vec<NameIndexEntry>,
+    /// `name_entries` sorted by `lower`, built on demand so prefix queries reduce
+    /// to a binary-searched range. Invalidated whenever a new symbol is bound.
+    name_index: OnceLock<Vec<NameIndexEntry>>,
+    /// References that point at another module's export and so cannot be bound
+    /// within this program. Resolved during [`link`](Self::link).
+    unresolved_symbols: Vec<UnresolvedSymbol>,
+    /// Maps source spans back to the symbol or reference they cover, in binding
+    /// order; sorted lazily into [`span_index`](Self::span_index).
+    span_entries: Vec<SpanIndexEntry>,
+    /// `span_entries` sorted by span start, built on demand so the innermost span
+    /// under a cursor can be binary-searched. Invalidated on every new entry.
+    span_index: OnceLock<Vec<SpanIndexEntry>>,
+}
+
+/// The thing a source [`Span`] refers to, as recorded in the span index: either a
+/// declaration or a resolved reference to one.
+#[derive(Debug, Clone, Copy)]
+enum SpanTarget {
+    Symbol(SymbolId),
+    Reference(ResolvedReferenceId),
+}
+
+/// A `(Span, target)` pair of the [`SymbolTable`] span index.
+#[derive(Debug, Clone, Copy)]
+struct SpanIndexEntry {
+    span: Span,
+    target: SpanTarget,
+}
+
+/// The `import * as ns` namespace symbol of a module.
+///
+/// Bundlers (e.g. rolldown) materialise one namespace symbol per module so that a
+/// cross-module reference can fall back to the whole namespace object when a named
+/// export cannot be resolved. The `symbol_id` is an ordinary entry of the owning
+/// module's [`SymbolTable`].
+#[derive(Debug, Clone, Copy)]
+pub struct NamespaceSymbol {
+    pub symbol_id: SymbolId,
+}
+
+impl NamespaceSymbol {
+    #[must_use]
+    pub fn new(symbol_id: SymbolId) -> Self {
+        Self { symbol_id }
+    }
+}
+
+/// A reference whose target lives in another module and therefore could not be
+/// bound by [`resolve_reference`](SymbolTable::resolve_reference).
+///
+/// It carries the `import * as ns` namespace of the exporting module so that
+/// [`link`](SymbolTable::link) can resolve `reference_name` against that module,
+/// falling back to the namespace symbol itself for a star import.
+#[derive(Debug, Clone)]
+pub struct UnresolvedSymbol {
+    pub resolved_reference_id: ResolvedReferenceId,
+    pub importee_namespace: NamespaceSymbol,
+    pub reference_name: Atom,
+}
+
+/// A single entry of the [`SymbolTable`] name index.
+///
+/// `lower` is a precomputed, lowercased copy of `name` so that case-insensitive
+/// prefix queries can binary-search the sorted index without allocating on every
+/// probe.
+#[derive(Debug, Clone)]
+struct NameIndexEntry {
+    name: Atom,
+    lower: String,
+    symbol_id: SymbolId,
 }
 
 impl Index<SymbolId> for SymbolTable {
     type Output = Symbol;
 
     fn index(&self, index: SymbolId) -> &Self::Output {
-        &self.symbols[index.index0()]
+        &self.symbols[index]
     }
 }
 
 impl IndexMut<SymbolId> for SymbolTable {
     fn index_mut(&mut self, index: SymbolId) -> &mut Self::Output {
-        &mut self.symbols[index.index0()]
+        &mut self.symbols[index]
     }
 }
 
@@ -38,18 +221,18 @@ impl Index<ResolvedReferenceId> for SymbolTable {
     type Output = ResolvedReference;
 
     fn index(&self, index: ResolvedReferenceId) -> &Self::Output {
-        &self.resolved_references[index.index0()]
+        &self.resolved_references[index]
     }
 }
 
 impl IndexMut<ResolvedReferenceId> for SymbolTable {
     fn index_mut(&mut self, index: ResolvedReferenceId) -> &mut Self::Output {
-        &mut self.resolved_references[index.index0()]
+        &mut self.resolved_references[index]
     }
 }
 
 impl Deref for SymbolTable {
-    type Target = Vec<Symbol>;
+    type Target = IndexVec<SymbolId, Symbol>;
 
     fn deref(&self) -> &Self::Target {
         &self.symbols
@@ -57,14 +240,37 @@ impl Deref for SymbolTable {
 }
 
 impl SymbolTable {
+    /// Construct a table pre-sized for a program whose symbol and reference counts
+    /// are already known (e.g. from a first AST walk), avoiding reallocation churn
+    /// during binding.
+    #[must_use]
+    pub fn with_capacity(symbols: usize, references: usize) -> Self {
+        Self {
+            symbols: IndexVec::with_capacity(symbols),
+            resolved_references: IndexVec::with_capacity(references),
+            name_entries: Vec::with_capacity(symbols),
+            span_entries: Vec::with_capacity(symbols + references),
+            ..Self::default()
+        }
+    }
+
+    /// Reserve capacity for `symbols` more symbols and `references` more resolved
+    /// references.
+    pub fn reserve(&mut self, symbols: usize, references: usize) {
+        self.symbols.reserve(symbols);
+        self.resolved_references.reserve(references);
+        self.name_entries.reserve(symbols);
+        self.span_entries.reserve(symbols + references);
+    }
+
     #[must_use]
-    pub fn symbols(&self) -> &Vec<Symbol> {
+    pub fn symbols(&self) -> &IndexVec<SymbolId, Symbol> {
         &self.symbols
     }
 
     #[must_use]
     pub fn get_symbol(&self, id: SymbolId) -> Option<&Symbol> {
-        self.symbols.get(id.index0())
+        self.symbols.get(id)
     }
 
     #[must_use]
@@ -75,20 +281,119 @@ impl SymbolTable {
         span: Span,
         flags: SymbolFlags,
     ) -> SymbolId {
-        let symbol_id = SymbolId::new(self.symbols.len() + 1);
-        let symbol = Symbol::new(symbol_id, declaration, name, span, flags);
+        let symbol_id = self.symbols.next_index();
+        let symbol = Symbol::new(symbol_id, declaration, name.clone(), span, flags);
         self.symbols.push(symbol);
+        self.index_name(name, symbol_id);
+        self.index_span(span, SpanTarget::Symbol(symbol_id));
         symbol_id
     }
 
+    /// Append a `(span, target)` pair to the span index in O(1). The sorted view
+    /// consumed by [`symbol_at`](Self::symbol_at) is rebuilt lazily on next query.
+    fn index_span(&mut self, span: Span, target: SpanTarget) {
+        self.span_entries.push(SpanIndexEntry { span, target });
+        self.span_index.take();
+    }
+
+    /// The span index sorted by span start, built on first use after a change.
+    fn span_index(&self) -> &[SpanIndexEntry] {
+        self.span_index.get_or_init(|| {
+            let mut entries = self.span_entries.clone();
+            entries.sort_by_key(|entry| entry.span.start);
+            entries
+        })
+    }
+
+    /// Append `name` to the name index in O(1). The sorted view consumed by
+    /// [`query_symbols`](Self::query_symbols) is rebuilt lazily on next query.
+    fn index_name(&mut self, name: Atom, symbol_id: SymbolId) {
+        let lower = name.to_lowercase();
+        self.name_entries.push(NameIndexEntry { name, lower, symbol_id });
+        self.name_index.take();
+    }
+
+    /// The name index sorted by the lowercased key, built on first use after a
+    /// change.
+    ///
+    /// Pushing entries unsorted during binding and sorting a single time here
+    /// keeps table construction O(n log n) rather than O(n²); cf. rust-analyzer's
+    /// `world_symbols`.
+    fn name_index(&self) -> &[NameIndexEntry] {
+        self.name_index.get_or_init(|| {
+            let mut entries = self.name_entries.clone();
+            entries.sort_by(|a, b| a.lower.cmp(&b.lower));
+            entries
+        })
+    }
+
+    /// Query the workspace symbol index for declarations matching `query`.
+    ///
+    /// Matching is a case-insensitive subsequence test (so `"fb"` matches
+    /// `fooBar`), modelled on rust-analyzer's `world_symbols`. Results are ranked:
+    /// an exact or prefix hit outscores a scattered subsequence, and matches that
+    /// land on a camelCase boundary are preferred. The returned `SymbolId`s are
+    /// ordered best match first.
     #[must_use]
-    pub fn resolved_references(&self) -> &Vec<ResolvedReference> {
+    pub fn query_symbols(&self, query: &str) -> Vec<SymbolId> {
+        let query = query.trim();
+        if query.is_empty() {
+            return self.name_index().iter().map(|entry| entry.symbol_id).collect();
+        }
+        // A never-cancelled token can only return `Ok`.
+        self.query_symbols_cancelable(query, &CancellationToken::new()).unwrap_or_default()
+    }
+
+    /// Like [`query_symbols`](Self::query_symbols), but abortable.
+    ///
+    /// In an editor a workspace query is routinely superseded by newer keystrokes.
+    /// `should_cancel` is polled every [`CANCEL_CHECK_INTERVAL`] candidates while
+    /// scanning and scoring; once it is set the scan returns `Err(Cancelled)` so
+    /// the caller can drop the stale work instead of blocking the UI thread.
+    pub fn query_symbols_cancelable(
+        &self,
+        query: &str,
+        should_cancel: &CancellationToken,
+    ) -> Result<Vec<SymbolId>, Cancelled> {
+        let query = query.trim();
+        let name_index = self.name_index();
+        if query.is_empty() {
+            return Ok(name_index.iter().map(|entry| entry.symbol_id).collect());
+        }
+        let lower = query.to_lowercase();
+
+        // Prefix hits are contiguous in the sorted index; find their range so they
+        // can be given a ranking bonus below.
+        let prefix_start =
+            name_index.partition_point(|entry| entry.lower.as_str() < lower.as_str());
+
+        let mut scored: Vec<(i32, &NameIndexEntry)> = Vec::new();
+        for (idx, entry) in name_index.iter().enumerate() {
+            if idx % CANCEL_CHECK_INTERVAL == 0 && should_cancel.is_cancelled() {
+                return Err(Cancelled);
+            }
+            let Some(mut score) = subsequence_score(&entry.name, query) else { continue };
+            if entry.lower == lower {
+                score += EXACT_MATCH_BONUS;
+            } else if idx >= prefix_start && entry.lower.starts_with(lower.as_str()) {
+                score += PREFIX_MATCH_BONUS;
+            }
+            scored.push((score, entry));
+        }
+
+        // Higher score first, then alphabetical for a stable, predictable order.
+        scored.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.lower.cmp(&b.1.lower)));
+        Ok(scored.into_iter().map(|(_, entry)| entry.symbol_id).collect())
+    }
+
+    #[must_use]
+    pub fn resolved_references(&self) -> &IndexVec<ResolvedReferenceId, ResolvedReference> {
         &self.resolved_references
     }
 
     #[must_use]
     pub fn get_resolved_reference(&self, id: ResolvedReferenceId) -> Option<&ResolvedReference> {
-        self.resolved_references.get(id.index0())
+        self.resolved_references.get(id)
     }
 
     /// Resolve all `references` to `symbol_id`
@@ -98,14 +403,286 @@ impl SymbolTable {
 
         self.resolved_references.reserve(additional_len);
         symbol.references.reserve(additional_len);
+        self.span_entries.reserve(additional_len);
 
+        // Remember the resolved spans to add to the span index once the `symbol`
+        // borrow is released below.
+        let mut spans = Vec::with_capacity(additional_len);
         for reference in references {
-            let resolved_reference_id =
-                ResolvedReferenceId::new(self.resolved_references.len() + 1);
             let resolved_reference = reference.resolve_to(symbol_id);
-            self.resolved_references.push(resolved_reference);
+            let span = resolved_reference.span;
+            let resolved_reference_id = self.resolved_references.push(resolved_reference);
+            spans.push((resolved_reference_id, span));
             // explicitly push to vector here in correspondence to the previous reserve call
             symbol.references.push(resolved_reference_id);
         }
+
+        for (resolved_reference_id, span) in spans {
+            self.index_span(span, SpanTarget::Reference(resolved_reference_id));
+        }
+    }
+
+    /// Find the symbol whose span most tightly encloses `offset`.
+    ///
+    /// When the cursor sits on a reference the reference's target symbol is
+    /// returned, so that \"go to definition\"/rename tooling gets the declaration
+    /// regardless of whether the cursor is on the binding or a use. Returns `None`
+    /// when no indexed span contains `offset`.
+    #[must_use]
+    pub fn symbol_at(&self, offset: u32) -> Option<SymbolId> {
+        // Entries are sorted by span start; every span that can contain `offset`
+        // starts at or before it, so scan that prefix from the end to reach the
+        // most deeply nested (largest start) match first.
+        let span_index = self.span_index();
+        let prefix = span_index.partition_point(|entry| entry.span.start <= offset);
+        span_index[..prefix]
+            .iter()
+            .rev()
+            .find(|entry| offset < entry.span.end)
+            .map(|entry| match entry.target {
+                SpanTarget::Symbol(symbol_id) => symbol_id,
+                SpanTarget::Reference(id) => self.resolved_references[id].symbol_id,
+            })
+    }
+
+    /// Yield the declaration span of `symbol_id` followed by the span of every
+    /// resolved reference to it, in resolution order.
+    ///
+    /// This is the set of occurrences an LSP rename must rewrite.
+    pub fn find_references(&self, symbol_id: SymbolId) -> impl Iterator<Item = Span> + '_ {
+        let symbol = &self[symbol_id];
+        std::iter::once(symbol.span).chain(
+            symbol
+                .references
+                .iter()
+                .map(move |id| self.resolved_references[*id].span),
+        )
+    }
+
+    /// Record a reference to `name` that resolves into another module, reached
+    /// through the `import * as ns` namespace `importee_namespace`.
+    ///
+    /// The returned `ResolvedReference` is provisionally bound to the namespace
+    /// symbol; [`link`](Self::link) rewrites it to the concrete export once every
+    /// module's table is available, keeping the namespace binding as the fallback
+    /// for a star import.
+    #[must_use]
+    pub fn create_unresolved(
+        &mut self,
+        name: Atom,
+        span: Span,
+        importee_namespace: NamespaceSymbol,
+    ) -> ResolvedReferenceId {
+        // A cross-module reference is still an ordinary `ResolvedReference`; build
+        // it through `Reference::resolve_to` (the only constructor the binder uses)
+        // rather than inventing a bare `new(SymbolId)`. It is provisionally bound
+        // to the namespace symbol and rewritten in [`link`](Self::link).
+        let reference = Reference::new(AstNodeId::new(0), name.clone(), span);
+        let resolved_reference_id =
+            self.resolved_references.push(reference.resolve_to(importee_namespace.symbol_id));
+        self.unresolved_symbols.push(UnresolvedSymbol {
+            resolved_reference_id,
+            importee_namespace,
+            reference_name: name,
+        });
+        resolved_reference_id
+    }
+
+    /// Second linking pass: resolve every cross-module reference against its
+    /// exporter and rewrite the `ResolvedReference` target in place.
+    ///
+    /// `resolver` is called with the exporter's namespace symbol and the imported
+    /// name and returns the concrete exported `SymbolId`, or `None` when the name
+    /// is not exported (a star import), in which case the reference stays bound to
+    /// the namespace symbol recorded by [`create_unresolved`](Self::create_unresolved).
+    pub fn link(&mut self, resolver: impl Fn(SymbolId, &Atom) -> Option<SymbolId>) {
+        for unresolved in std::mem::take(&mut self.unresolved_symbols) {
+            let namespace = unresolved.importee_namespace.symbol_id;
+            let target = resolver(namespace, &unresolved.reference_name).unwrap_or(namespace);
+            self[unresolved.resolved_reference_id].symbol_id = target;
+        }
+    }
+}
+
+/// How often [`SymbolTable::query_symbols_cancelable`] polls its cancellation
+/// token while scanning candidates.
+const CANCEL_CHECK_INTERVAL: usize = 256;
+
+/// A cooperative, thread-safe cancellation flag threaded through long-running
+/// symbol queries.
+///
+/// The querying thread polls [`is_cancelled`](Self::is_cancelled) periodically;
+/// any other thread (e.g. the one handling the next keystroke) calls
+/// [`cancel`](Self::cancel) to abort the in-flight query.
+#[derive(Debug, Default)]
+pub struct CancellationToken {
+    cancelled: AtomicBool,
+}
+
+impl CancellationToken {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Signal that any query observing this token should abort.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+/// Returned by a query that was aborted through its [`CancellationToken`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cancelled;
+
+/// Score bonus awarded to an exact (case-insensitive) name match.
+const EXACT_MATCH_BONUS: i32 = 1000;
+/// Score bonus awarded when the query is a prefix of the candidate name.
+const PREFIX_MATCH_BONUS: i32 = 500;
+/// Score bonus awarded when a matched character sits on a camelCase boundary.
+const CAMEL_BOUNDARY_BONUS: i32 = 10;
+
+/// Case-insensitive subsequence match of `query` against `candidate`.
+///
+/// Returns `None` when `query` is not a subsequence of `candidate`, otherwise a
+/// score where contiguous matches and matches landing on a camelCase boundary
+/// (an uppercase letter, or the first character after a non-alphanumeric one)
+/// rank higher. Shorter candidates also score slightly higher so that `get`
+/// ranks `get` above `getChildAtOffset`.
+fn subsequence_score(candidate: &Atom, query: &str) -> Option<i32> {
+    let mut score = 0;
+    let mut query_chars = query.chars().map(|c| c.to_ascii_lowercase()).peekable();
+    let mut prev_matched = false;
+    let mut prev_char: Option<char> = None;
+
+    for ch in candidate.chars() {
+        let Some(&next) = query_chars.peek() else { break };
+        if ch.to_ascii_lowercase() == next {
+            let on_boundary = ch.is_ascii_uppercase()
+                || prev_char.map_or(true, |p| !p.is_ascii_alphanumeric());
+            if on_boundary {
+                score += CAMEL_BOUNDARY_BONUS;
+            }
+            if prev_matched {
+                score += 1;
+            }
+            score += 1;
+            prev_matched = true;
+            query_chars.next();
+        } else {
+            prev_matched = false;
+        }
+        prev_char = Some(ch);
+    }
+
+    if query_chars.peek().is_some() {
+        return None;
+    }
+    // Prefer shorter candidates among equally good subsequence matches.
+    Some(score - candidate.len() as i32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Bind a symbol named `name` over `[start, end)` and return its id.
+    fn create(table: &mut SymbolTable, name: &str, start: u32, end: u32) -> SymbolId {
+        table.create(AstNodeId::new(0), Atom::from(name), Span::new(start, end), SymbolFlags::empty())
+    }
+
+    /// A reference to `name` over `[start, end)`.
+    fn reference(name: &str, start: u32, end: u32) -> Reference {
+        Reference::new(AstNodeId::new(0), Atom::from(name), Span::new(start, end))
+    }
+
+    #[test]
+    fn query_symbols_ranks_exact_prefix_then_fuzzy() {
+        let mut table = SymbolTable::default();
+        let exact = create(&mut table, "get", 0, 3);
+        let prefix = create(&mut table, "getter", 10, 16);
+        // `get` is a mid-word subsequence of `wiget`, not a prefix, so it must
+        // rank below the prefix hit regardless of the length tiebreak.
+        let fuzzy = create(&mut table, "wiget", 20, 25);
+
+        let results = table.query_symbols("get");
+        // Exact match first, then the prefix hit, then the mid-word subsequence.
+        assert_eq!(results, vec![exact, prefix, fuzzy]);
+    }
+
+    #[test]
+    fn query_symbols_is_case_insensitive_subsequence() {
+        let mut table = SymbolTable::default();
+        let foo_bar = create(&mut table, "fooBar", 0, 6);
+        create(&mut table, "baz", 10, 13);
+
+        assert_eq!(table.query_symbols("fb"), vec![foo_bar]);
+        assert!(table.query_symbols("qux").is_empty());
+    }
+
+    #[test]
+    fn symbol_at_returns_innermost_span() {
+        let mut table = SymbolTable::default();
+        let outer = create(&mut table, "outer", 0, 100);
+        let inner = create(&mut table, "inner", 10, 20);
+
+        assert_eq!(table.symbol_at(15), Some(inner));
+        assert_eq!(table.symbol_at(50), Some(outer));
+        assert_eq!(table.symbol_at(200), None);
+    }
+
+    #[test]
+    fn find_references_yields_declaration_then_uses() {
+        let mut table = SymbolTable::default();
+        let symbol = create(&mut table, "x", 0, 1);
+        table.resolve_reference(vec![reference("x", 10, 11), reference("x", 20, 21)], symbol);
+
+        let spans: Vec<_> = table.find_references(symbol).collect();
+        assert_eq!(spans, vec![Span::new(0, 1), Span::new(10, 11), Span::new(20, 21)]);
+    }
+
+    #[test]
+    fn link_rewrites_target_to_export() {
+        let mut exporter = SymbolTable::default();
+        let exported = create(&mut exporter, "value", 0, 5);
+
+        let mut importer = SymbolTable::default();
+        let ns = NamespaceSymbol::new(create(&mut importer, "ns", 0, 2));
+        let reference_id = importer.create_unresolved(Atom::from("value"), Span::new(10, 15), ns);
+
+        // The resolver maps the name against the exporter's table; `link` only
+        // rewrites the reference target, it does not touch the importer's symbols.
+        importer.link(|_namespace, name| (name == "value").then_some(exported));
+
+        assert_eq!(importer[reference_id].symbol_id, exported);
+    }
+
+    #[test]
+    fn link_falls_back_to_namespace_for_star_import() {
+        let mut table = SymbolTable::default();
+        let ns = NamespaceSymbol::new(create(&mut table, "ns", 0, 2));
+        let reference_id = table.create_unresolved(Atom::from("missing"), Span::new(10, 17), ns);
+
+        // Resolver cannot find the name: the reference keeps the namespace binding.
+        table.link(|_namespace, _name| None);
+
+        assert_eq!(table[reference_id].symbol_id, ns.symbol_id);
+    }
+
+    #[test]
+    fn cancelled_query_returns_err() {
+        let mut table = SymbolTable::default();
+        for i in 0..1000 {
+            create(&mut table, &format!("symbol{i}"), i * 10, i * 10 + 6);
+        }
+
+        let token = CancellationToken::new();
+        token.cancel();
+        assert_eq!(table.query_symbols_cancelable("symbol", &token), Err(Cancelled));
     }
 }